@@ -68,7 +68,6 @@ pub trait PyContextVarMethods<'py> {
     fn set<V>(&self, value: V) -> PyResult<Bound<'py, PyContextToken>>
     where
         V: IntoPyObject<'py>;
-    #[allow(dead_code)]
     fn reset(&self, token: &Bound<'py, PyContextToken>) -> PyResult<()>;
 }
 
@@ -1,11 +1,14 @@
 #![deny(rust_2018_idioms)]
 
 use std::cmp::Reverse;
+use std::collections::VecDeque;
 use std::sync::atomic::{self, AtomicUsize};
+use std::sync::Mutex;
 
-use contextvars::{PyContextVar, PyContextVarMethods};
+use contextvars::{PyContextToken, PyContextVar, PyContextVarMethods};
 use pyo3::exceptions::{
-    PyAssertionError, PyException, PyKeyError, PyLookupError, PyNotImplementedError, PyTypeError,
+    PyAssertionError, PyAttributeError, PyException, PyKeyError, PyLookupError,
+    PyNotImplementedError, PyTypeError,
 };
 use pyo3::prelude::*;
 use pyo3::sync::PyOnceLock;
@@ -37,6 +40,9 @@ impl LazyPyImport {
 pub static WEAKREF_WEAK_KEY_DICTIONARY: LazyPyImport =
     LazyPyImport::new("weakref", "WeakKeyDictionary");
 pub static BUILTINS_REVERSED: LazyPyImport = LazyPyImport::new("builtins", "reversed");
+pub static THREADING_LOCAL: LazyPyImport = LazyPyImport::new("threading", "local");
+pub static THREADING_GET_IDENT: LazyPyImport = LazyPyImport::new("threading", "get_ident");
+pub static WEAKREF_REF: LazyPyImport = LazyPyImport::new("weakref", "ref");
 
 #[pyclass(module = "logbook._speedups", sequence, weakref)]
 pub struct FrozenSequence {
@@ -55,6 +61,29 @@ impl FrozenSequence {
     fn empty(py: Python<'_>) -> Self {
         Self::new(PyTuple::empty(py))
     }
+
+    /// Coerces `value` to a `PyTuple`, reusing another `FrozenSequence`'s
+    /// backing tuple directly or collecting any other iterable.
+    ///
+    /// Returns `Ok(None)` rather than propagating the `TypeError` CPython
+    /// raises for a non-iterable `value`, so `__add__`/`__radd__` can return
+    /// `NotImplemented` for an incompatible operand the way real sequence
+    /// types do, instead of leaking that `TypeError` straight through.
+    fn coerce_tuple<'py>(
+        py: Python<'py>,
+        value: &Bound<'py, PyAny>,
+    ) -> PyResult<Option<Bound<'py, PyTuple>>> {
+        if let Ok(other) = value.extract::<PyRef<'_, FrozenSequence>>() {
+            return Ok(Some(other.items.bind(py).clone()));
+        }
+        let iterator = match value.try_iter() {
+            Ok(iterator) => iterator,
+            Err(err) if err.is_instance(py, &py.get_type::<PyTypeError>()) => return Ok(None),
+            Err(err) => return Err(err),
+        };
+        let items: Vec<Bound<'_, PyAny>> = iterator.collect::<PyResult<_>>()?;
+        Ok(Some(PyTuple::new(py, items)?))
+    }
 }
 
 #[pymethods]
@@ -121,6 +150,83 @@ impl FrozenSequence {
         };
         Ok(format!("FrozenSequence({})", s))
     }
+
+    fn __add__(&self, py: Python<'_>, other: &Bound<'_, PyAny>) -> PyResult<Py<PyAny>> {
+        let Some(other) = Self::coerce_tuple(py, other)? else {
+            return Ok(py.NotImplemented());
+        };
+        let combined = self
+            .items
+            .bind(py)
+            .as_sequence()
+            .concat(&other.as_sequence())?
+            .to_tuple()?;
+        Ok(Bound::new(py, Self::new(combined))?.into_any().unbind())
+    }
+
+    fn __radd__(&self, py: Python<'_>, other: &Bound<'_, PyAny>) -> PyResult<Py<PyAny>> {
+        let Some(other) = Self::coerce_tuple(py, other)? else {
+            return Ok(py.NotImplemented());
+        };
+        let combined = other
+            .as_sequence()
+            .concat(&self.items.bind(py).as_sequence())?
+            .to_tuple()?;
+        Ok(Bound::new(py, Self::new(combined))?.into_any().unbind())
+    }
+
+    fn __mul__(&self, py: Python<'_>, count: isize) -> PyResult<Self> {
+        let repeated = self
+            .items
+            .bind(py)
+            .as_sequence()
+            .repeat(count.max(0) as usize)?
+            .to_tuple()?;
+        Ok(Self::new(repeated))
+    }
+
+    fn __rmul__(&self, py: Python<'_>, count: isize) -> PyResult<Self> {
+        self.__mul__(py, count)
+    }
+
+    fn __lt__(&self, py: Python<'_>, other: &Self) -> PyResult<bool> {
+        self.items.bind(py).lt(other.items.bind(py))
+    }
+
+    fn __le__(&self, py: Python<'_>, other: &Self) -> PyResult<bool> {
+        self.items.bind(py).le(other.items.bind(py))
+    }
+
+    fn __gt__(&self, py: Python<'_>, other: &Self) -> PyResult<bool> {
+        self.items.bind(py).gt(other.items.bind(py))
+    }
+
+    fn __ge__(&self, py: Python<'_>, other: &Self) -> PyResult<bool> {
+        self.items.bind(py).ge(other.items.bind(py))
+    }
+
+    #[pyo3(signature = (value, start = 0, stop = None))]
+    fn index(
+        &self,
+        py: Python<'_>,
+        value: &Bound<'_, PyAny>,
+        start: isize,
+        stop: Option<isize>,
+    ) -> PyResult<usize> {
+        let items = self.items.bind(py);
+        let result = match stop {
+            Some(stop) => items.call_method1(intern!(py, "index"), (value, start, stop))?,
+            None => items.call_method1(intern!(py, "index"), (value, start))?,
+        };
+        result.extract()
+    }
+
+    fn count(&self, py: Python<'_>, value: &Bound<'_, PyAny>) -> PyResult<usize> {
+        self.items
+            .bind(py)
+            .call_method1(intern!(py, "count"), (value,))?
+            .extract()
+    }
 }
 
 const MAX_CONTEXT_OBJECT_CACHE: usize = 256;
@@ -128,8 +234,17 @@ const MAX_CONTEXT_OBJECT_CACHE: usize = 256;
 #[pyclass(module = "logbook._speedups")]
 pub struct ContextStackManager {
     global: Py<PyList>,
+    /// A `threading.local()` instance whose `stack` attribute is lazily
+    /// populated with a per-thread `PyList`, mirroring `global` but scoped to
+    /// a single worker thread instead of the whole process.
+    thread_stack: Py<PyAny>,
     context_stack: Py<PyContextVar>,
     cache: Py<PyMapping>,
+    /// Most-recently-used order for `cache`'s keys, as `weakref.ref` objects
+    /// pointing at the `FrozenSequence` keys themselves, oldest first. Used to
+    /// evict entries one at a time instead of wiping the whole cache once it
+    /// reaches `MAX_CONTEXT_OBJECT_CACHE`.
+    cache_order: Mutex<VecDeque<Py<PyAny>>>,
     stack_count: AtomicUsize,
 }
 
@@ -137,6 +252,111 @@ impl ContextStackManager {
     fn stackop(&self) -> usize {
         self.stack_count.fetch_add(1, atomic::Ordering::Relaxed)
     }
+
+    /// Returns this thread's handler list, creating it on first use.
+    fn thread_list<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyList>> {
+        let local = self.thread_stack.bind(py);
+        match local.getattr(intern!(py, "stack")) {
+            Ok(list) => Ok(list.downcast_into()?),
+            Err(err) if err.is_instance(py, &py.get_type::<PyAttributeError>()) => {
+                let list = PyList::empty(py);
+                local.setattr(intern!(py, "stack"), &list)?;
+                Ok(list)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Marks `stack` as the most-recently-used cache key, recording it in
+    /// `cache_order` if it isn't tracked yet.
+    ///
+    /// `cache` is a `WeakKeyDictionary`, so its entries can self-prune at any
+    /// time — far more often than `cache` ever reaching `MAX_CONTEXT_OBJECT_CACHE`,
+    /// since context-local stacks routinely die the moment a `with handler:`
+    /// block exits. Every call here scans the whole order and drops any
+    /// weakref that's already dead, so `cache_order`'s length stays tied to
+    /// `cache`'s actual live size instead of only shrinking at capacity.
+    fn touch_cache_entry(&self, py: Python<'_>, stack: &Bound<'_, FrozenSequence>) -> PyResult<()> {
+        let mut order = self.cache_order.lock().unwrap();
+        let previous = std::mem::take(&mut *order);
+        let mut existing = None;
+        for weak in previous {
+            let resolved = weak.bind(py).call0()?;
+            if resolved.is_none() {
+                continue;
+            }
+            if existing.is_none() && resolved.is(stack) {
+                existing = Some(weak);
+            } else {
+                order.push_back(weak);
+            }
+        }
+        let entry = match existing {
+            Some(weak) => weak,
+            None => WEAKREF_REF.get(py)?.call1((stack,))?.unbind(),
+        };
+        order.push_back(entry);
+        Ok(())
+    }
+
+    /// Drops every cache entry along with the LRU order tracking it.
+    fn clear_cache(&self, py: Python<'_>) -> PyResult<()> {
+        self.cache.bind(py).call_method0(intern!(py, "clear"))?;
+        self.cache_order.lock().unwrap().clear();
+        Ok(())
+    }
+
+    /// Evicts least-recently-used cache entries until `cache` has room for one
+    /// more, pruning any tracked keys whose weakref has already died.
+    fn evict_lru(&self, py: Python<'_>, cache: &Bound<'_, PyMapping>) -> PyResult<()> {
+        let mut order = self.cache_order.lock().unwrap();
+        while cache.len()? >= MAX_CONTEXT_OBJECT_CACHE {
+            let Some(weak) = order.pop_front() else {
+                break;
+            };
+            let resolved = weak.bind(py).call0()?;
+            if resolved.is_none() {
+                continue;
+            }
+            // The key may already be gone (e.g. the `WeakKeyDictionary`'s own
+            // callback beat us to it); either way it's no longer in `cache`.
+            let _ = cache.del_item(resolved);
+        }
+        Ok(())
+    }
+
+    /// Extends `context_stack` with a single `(counter, obj, token)` frame,
+    /// minting the token by re-setting the var to its own current value.
+    ///
+    /// Shared by `push_context` (fresh counter, fresh context) and snapshot
+    /// restoration (replaying a captured counter in whatever context/thread is
+    /// currently running), so that in both cases the minted token is valid to
+    /// `reset()` wherever this call happened to run.
+    fn push_frame<'py>(
+        &self,
+        py: Python<'py>,
+        context_stack: &Bound<'py, PyContextVar>,
+        counter: usize,
+        obj: Bound<'py, PyAny>,
+    ) -> PyResult<()> {
+        let Some(current) = context_stack.get(None)? else {
+            return Err(PyLookupError::new_err(context_stack.clone().unbind()));
+        };
+
+        let token = context_stack.set(&current)?;
+
+        let stack: PyRef<'_, FrozenSequence> = current.extract()?;
+        let items = stack.items.bind(py);
+        let new_item = (counter, obj, token).into_pyobject(py)?;
+
+        let stack = items
+            .as_sequence()
+            .concat(&((new_item,).into_pyobject(py)?.into_sequence()))?
+            .to_tuple()?;
+
+        context_stack.set(FrozenSequence::new(stack))?;
+        Ok(())
+    }
 }
 
 #[pymethods]
@@ -151,12 +371,14 @@ impl ContextStackManager {
         let stack = Bound::new(py, FrozenSequence::empty(py))?;
         Ok(Self {
             global: PyList::empty(py).unbind(),
+            thread_stack: THREADING_LOCAL.get(py)?.call0()?.unbind(),
             context_stack: PyContextVar::new_with_default(py, "stack", stack)?.unbind(),
             cache: WEAKREF_WEAK_KEY_DICTIONARY
                 .get(py)?
                 .call0()?
                 .downcast_into()?
                 .unbind(),
+            cache_order: Mutex::new(VecDeque::new()),
             stack_count: AtomicUsize::new(0),
         })
     }
@@ -166,6 +388,11 @@ impl ContextStackManager {
         self.global.clone_ref(py)
     }
 
+    #[getter(_thread_stack)]
+    fn get_thread_stack(&self, py: Python<'_>) -> Py<PyAny> {
+        self.thread_stack.clone_ref(py)
+    }
+
     #[getter(_context_stack)]
     fn get_context_stack(&self, py: Python<'_>) -> Py<PyContextVar> {
         self.context_stack.clone_ref(py)
@@ -182,69 +409,85 @@ impl ContextStackManager {
             return Err(PyLookupError::new_err(context_stack.clone().unbind()));
         };
         let stack = stack.downcast_into::<FrozenSequence>()?;
+        let thread_ident: usize = THREADING_GET_IDENT.get(py)?.call0()?.extract()?;
         let cache = self.cache.bind(py);
+
+        // A cached merge is only valid for the thread it was computed for: two
+        // threads can observe the same context `FrozenSequence` while holding
+        // different thread-local handler stacks, so the thread identity that
+        // produced a merge travels alongside it in the cache.
         match cache.get_item(&stack) {
-            Ok(objects) => Ok(objects.try_iter()?.unbind()),
-            Err(err) if err.is_instance(py, &py.get_type::<PyKeyError>()) => {
-                if cache.len()? >= MAX_CONTEXT_OBJECT_CACHE {
-                    cache.call_method0(intern!(py, "clear"))?;
+            Ok(entry) => {
+                let (cached_ident, objects): (usize, Bound<'_, PyTuple>) = entry.extract()?;
+                if cached_ident == thread_ident {
+                    self.touch_cache_entry(py, &stack)?;
+                    return Ok(objects.try_iter()?.unbind());
                 }
+            }
+            Err(err) if err.is_instance(py, &py.get_type::<PyKeyError>()) => {}
+            Err(err) => return Err(err),
+        }
 
-                let global = self.global.bind(py);
-                let mut stack_objects: Vec<(usize, Bound<'_, PyAny>)> = global
-                    .try_iter()?
-                    .chain(stack.try_iter()?)
-                    .map(|item| item.and_then(|item| item.extract()))
-                    .collect::<PyResult<_>>()?;
-                stack_objects.sort_by_key(|item| Reverse(item.0));
-                let objects = PyTuple::new(py, stack_objects.into_iter().map(|item| item.1))?;
+        if cache.len()? >= MAX_CONTEXT_OBJECT_CACHE {
+            self.evict_lru(py, cache)?;
+        }
 
-                cache.set_item(stack, objects.clone())?;
+        let global = self.global.bind(py);
+        let thread_list = self.thread_list(py)?;
+        // Context frames carry a trailing `PyContextToken` that global and
+        // thread frames don't, so pull the `(counter, obj)` pair out by
+        // position instead of relying on a fixed tuple arity.
+        let extract_frame = |item: PyResult<Bound<'_, PyAny>>| -> PyResult<(usize, Bound<'_, PyAny>)> {
+            let item = item?;
+            Ok((item.get_item(0)?.extract()?, item.get_item(1)?))
+        };
+        let mut stack_objects: Vec<(usize, Bound<'_, PyAny>)> = global
+            .try_iter()?
+            .chain(thread_list.try_iter()?)
+            .chain(stack.try_iter()?)
+            .map(extract_frame)
+            .collect::<PyResult<_>>()?;
+        stack_objects.sort_by_key(|item| Reverse(item.0));
+        let objects = PyTuple::new(py, stack_objects.into_iter().map(|item| item.1))?;
 
-                Ok(objects.try_iter()?.unbind())
-            }
-            Err(err) => Err(err),
-        }
+        cache.set_item(&stack, (thread_ident, objects.clone()))?;
+        self.touch_cache_entry(py, &stack)?;
+
+        Ok(objects.try_iter()?.unbind())
     }
 
     fn push_context<'py>(&self, py: Python<'py>, obj: Bound<'py, PyAny>) -> PyResult<()> {
         let context_stack = self.context_stack.bind(py);
-        let new_item = (self.stackop(), obj).into_pyobject(py)?;
-        let Some(stack) = context_stack.get(None)? else {
-            return Err(PyLookupError::new_err(context_stack.clone().unbind()));
-        };
-        let stack: PyRef<'_, FrozenSequence> = stack.extract()?;
-        let items = stack.items.bind(py);
-
-        let stack = items
-            .as_sequence()
-            .concat(&((new_item,).into_pyobject(py)?.into_sequence()))?
-            .to_tuple()?;
-
-        let stack = FrozenSequence::new(stack);
-        context_stack.set(stack)?;
-        Ok(())
+        self.push_frame(py, context_stack, self.stackop(), obj)
     }
 
     fn pop_context<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
         let context_stack = self.context_stack.bind(py);
-        let Some(stack) = context_stack.get(None)? else {
+        let Some(current) = context_stack.get(None)? else {
             return Err(PyLookupError::new_err(context_stack.clone().unbind()));
         };
-        let stack: PyRef<'_, FrozenSequence> = stack.extract()?;
+        let stack: PyRef<'_, FrozenSequence> = current.extract()?;
         let items = stack.items.bind(py);
-        let Some((popped, remaining)) = items.as_slice().split_last() else {
+        let Some(top) = items.as_slice().last() else {
             return Err(PyAssertionError::new_err("no objects on stack"));
         };
-        let stack = FrozenSequence::new(PyTuple::new(py, remaining)?);
-        context_stack.set(stack)?;
-        popped.get_item(1)
+        let popped = top.get_item(1)?;
+        let Ok(token) = top.get_item(2) else {
+            return Err(PyAssertionError::new_err("no objects on stack"));
+        };
+        let token: Bound<'_, PyContextToken> = token.downcast_into()?;
+
+        // Strict LIFO nesting (guaranteed by `StackedObject.__enter__`/`__exit__`)
+        // means this frame's token always undoes exactly the push that created it,
+        // restoring the previous `FrozenSequence` in place without allocating.
+        context_stack.reset(&token)?;
+        Ok(popped)
     }
 
     fn push_application(&self, py: Python<'_>, obj: Bound<'_, PyAny>) -> PyResult<()> {
         let new_item = (self.stackop(), obj).into_pyobject(py)?;
         self.global.bind(py).append(new_item)?;
-        self.cache.bind(py).call_method0(intern!(py, "clear"))?;
+        self.clear_cache(py)?;
         Ok(())
     }
 
@@ -254,9 +497,178 @@ impl ContextStackManager {
             return Err(PyAssertionError::new_err("no objects on application stack"));
         }
         let popped = global.call_method0(intern!(py, "pop"))?;
-        self.cache.bind(py).call_method0(intern!(py, "clear"))?;
+        self.clear_cache(py)?;
+        popped.get_item(1)
+    }
+
+    fn push_thread(&self, py: Python<'_>, obj: Bound<'_, PyAny>) -> PyResult<()> {
+        let new_item = (self.stackop(), obj).into_pyobject(py)?;
+        self.thread_list(py)?.append(new_item)?;
+        self.clear_cache(py)?;
+        Ok(())
+    }
+
+    fn pop_thread<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let thread_list = self.thread_list(py)?;
+        if thread_list.is_empty() {
+            return Err(PyAssertionError::new_err("no objects on thread stack"));
+        }
+        let popped = thread_list.call_method0(intern!(py, "pop"))?;
+        self.clear_cache(py)?;
         popped.get_item(1)
     }
+
+    /// Captures the current handler configuration (the `global` stack, the
+    /// calling thread's `thread_list`, and the current context's
+    /// `FrozenSequence`, `stackop` counters and all) so it can be reinstated
+    /// elsewhere, e.g. in a freshly spawned task or thread that doesn't
+    /// inherit the current `contextvars.Context` — or the calling thread's
+    /// `threading.local()` storage either.
+    fn snapshot(&self, py: Python<'_>) -> PyResult<ContextSnapshot> {
+        let context_stack = self.context_stack.bind(py);
+        let Some(stack) = context_stack.get(None)? else {
+            return Err(PyLookupError::new_err(context_stack.clone().unbind()));
+        };
+        let stack = stack.downcast_into::<FrozenSequence>()?;
+        let global = PyTuple::new(py, self.global.bind(py).try_iter()?.collect::<PyResult<Vec<_>>>()?)?;
+        let thread = PyTuple::new(py, self.thread_list(py)?.try_iter()?.collect::<PyResult<Vec<_>>>()?)?;
+        Ok(ContextSnapshot {
+            global: global.unbind(),
+            thread: thread.unbind(),
+            context: stack.unbind(),
+        })
+    }
+
+    fn apply_snapshot(self_: Py<Self>, snapshot: Py<ContextSnapshot>) -> ApplySnapshot {
+        ApplySnapshot {
+            manager: self_,
+            snapshot,
+            token: Mutex::new(None),
+        }
+    }
+}
+
+#[pyclass(module = "logbook._speedups")]
+pub struct ContextSnapshot {
+    global: Py<PyTuple>,
+    /// The captured thread's `thread_list` contents, as `(counter, obj)`
+    /// pairs. Genuinely thread-local (unlike `global`), so unlike `global`
+    /// it can't just be left for the restoring thread to see on its own —
+    /// `ApplySnapshot::__enter__` replays these into the restored
+    /// `context_stack` alongside the captured context frames.
+    thread: Py<PyTuple>,
+    context: Py<FrozenSequence>,
+}
+
+#[pymethods]
+impl ContextSnapshot {
+    #[getter(_global)]
+    fn get_global(&self, py: Python<'_>) -> Py<PyTuple> {
+        self.global.clone_ref(py)
+    }
+
+    #[getter(_thread)]
+    fn get_thread(&self, py: Python<'_>) -> Py<PyTuple> {
+        self.thread.clone_ref(py)
+    }
+
+    #[getter(_context)]
+    fn get_context(&self, py: Python<'_>) -> Py<FrozenSequence> {
+        self.context.clone_ref(py)
+    }
+}
+
+#[pyclass(module = "logbook._speedups")]
+pub struct ApplySnapshot {
+    manager: Py<ContextStackManager>,
+    snapshot: Py<ContextSnapshot>,
+    token: Mutex<Option<Py<PyContextToken>>>,
+}
+
+#[pymethods]
+impl ApplySnapshot {
+    fn __enter__(&self, py: Python<'_>) -> PyResult<()> {
+        let manager = self.manager.bind(py).borrow();
+        let snapshot = self.snapshot.bind(py).borrow();
+        let context_stack = manager.context_stack.bind(py);
+
+        // `global` is process-wide state, not context-local, so it's already
+        // visible regardless of which context/thread is running; only the
+        // context-local frames need restoring here. Re-merging the snapshot's
+        // `global` copy in too would double-count every application handler
+        // with whatever `global` currently holds.
+        //
+        // `thread_list` is the opposite: it's genuinely thread-local, so the
+        // restoring thread's own `thread_list` won't show the captured
+        // thread's handlers on its own. There's also nowhere to put them
+        // *as* thread-local state scoped to just this `with` block, so they're
+        // folded into the replayed context frames below instead, alongside
+        // the captured `context_stack`, ordered by their original `stackop`
+        // counters.
+        //
+        // The snapshot's frames still carry `PyContextToken`s minted wherever
+        // `snapshot()` was called, which are only valid to `reset()` in that
+        // original `Context`. Since the whole point of apply_snapshot is
+        // restoring this state somewhere else (a spawned task/thread that
+        // doesn't inherit that `Context`), reusing those tokens verbatim makes
+        // a `pop_context()` call from inside this block raise `ValueError`
+        // ("...was created in a different Context"). Start from empty and
+        // replay each frame through `push_frame` instead, so every token in
+        // the restored stack is freshly minted in *this* context.
+        let outer_token = context_stack.set(FrozenSequence::empty(py))?;
+
+        // `with` only calls `__exit__` once `__enter__` has returned, so a
+        // failure anywhere below (a malformed frame, say) would otherwise
+        // leave `context_stack` stuck on a partially-rebuilt stack for the
+        // rest of this Context, with no `__exit__` call coming to fix it.
+        // Roll back to `outer_token` ourselves on that path instead, and only
+        // hand it to `__exit__` once replay has fully succeeded.
+        let replayed = (|| -> PyResult<()> {
+            let extract_frame =
+                |item: PyResult<Bound<'_, PyAny>>| -> PyResult<(usize, Bound<'_, PyAny>)> {
+                    let item = item?;
+                    Ok((item.get_item(0)?.extract()?, item.get_item(1)?))
+                };
+            let mut frames: Vec<(usize, Bound<'_, PyAny>)> = snapshot
+                .context
+                .bind(py)
+                .items
+                .bind(py)
+                .try_iter()?
+                .chain(snapshot.thread.bind(py).try_iter()?)
+                .map(extract_frame)
+                .collect::<PyResult<_>>()?;
+            frames.sort_by_key(|(counter, _)| *counter);
+
+            for (counter, obj) in frames {
+                manager.push_frame(py, context_stack, counter, obj)?;
+            }
+            Ok(())
+        })();
+
+        if let Err(err) = replayed {
+            context_stack.reset(&outer_token)?;
+            return Err(err);
+        }
+
+        *self.token.lock().unwrap() = Some(outer_token.unbind());
+        Ok(())
+    }
+
+    fn __exit__(
+        &self,
+        py: Python<'_>,
+        _exc_type: Option<&Bound<'_, PyType>>,
+        _exc_val: Option<&Bound<'_, PyException>>,
+        _exc_tb: Option<&Bound<'_, PyTraceback>>,
+    ) -> PyResult<()> {
+        let Some(token) = self.token.lock().unwrap().take() else {
+            return Ok(());
+        };
+        let manager = self.manager.bind(py).borrow();
+        manager.context_stack.bind(py).reset(token.bind(py))?;
+        Ok(())
+    }
 }
 
 #[pyclass(module = "logbook._speedups")]
@@ -292,6 +704,37 @@ impl ApplicationBound {
     }
 }
 
+#[pyclass(module = "logbook._speedups")]
+pub struct ThreadBound {
+    obj: Py<PyAny>,
+}
+
+impl ThreadBound {
+    fn new(obj: Py<PyAny>) -> Self {
+        Self { obj }
+    }
+}
+
+#[pymethods]
+impl ThreadBound {
+    fn __enter__(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        let obj = self.obj.bind(py);
+        obj.call_method0(intern!(py, "push_thread"))?;
+        Ok(obj.clone().unbind())
+    }
+
+    fn __exit__(
+        &self,
+        py: Python<'_>,
+        _exc_type: Option<&Bound<'_, PyType>>,
+        _exc_val: Option<&Bound<'_, PyException>>,
+        _exc_tb: Option<&Bound<'_, PyTraceback>>,
+    ) -> PyResult<()> {
+        self.obj.bind(py).call_method0(intern!(py, "pop_thread"))?;
+        Ok(())
+    }
+}
+
 #[pyclass(module = "logbook._speedups", subclass)]
 pub struct StackedObject;
 
@@ -319,6 +762,14 @@ impl StackedObject {
         Err(PyNotImplementedError::new_err(()))
     }
 
+    fn push_thread(&self) -> PyResult<()> {
+        Err(PyNotImplementedError::new_err(()))
+    }
+
+    fn pop_thread(&self) -> PyResult<()> {
+        Err(PyNotImplementedError::new_err(()))
+    }
+
     fn __enter__(self_: Py<Self>, py: Python<'_>) -> PyResult<Py<Self>> {
         self_.bind(py).call_method0(intern!(py, "push_context"))?;
         Ok(self_)
@@ -338,6 +789,10 @@ impl StackedObject {
     fn applicationbound(self_: Py<Self>) -> ApplicationBound {
         ApplicationBound::new(self_.into_any())
     }
+
+    fn threadbound(self_: Py<Self>) -> ThreadBound {
+        ThreadBound::new(self_.into_any())
+    }
 }
 
 /// Similar to Option but the pyo3 conversion traits are not implemented for it,
@@ -456,3 +911,297 @@ fn _speedups(m: &Bound<'_, PyModule>) -> PyResult<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use pyo3::types::{PyCFunction, PyString};
+
+    use super::*;
+
+    fn new_manager(py: Python<'_>) -> PyResult<Bound<'_, ContextStackManager>> {
+        Bound::new(
+            py,
+            ContextStackManager::__new__(py, PyTuple::empty(py).as_any(), None)?,
+        )
+    }
+
+    fn as_strings(py: Python<'_>, objects: Py<PyIterator>) -> PyResult<Vec<String>> {
+        objects.into_bound(py).map(|item| item?.extract()).collect()
+    }
+
+    #[test]
+    fn push_thread_and_pop_thread_round_trip() -> PyResult<()> {
+        Python::initialize();
+        Python::attach(|py| {
+            let manager = new_manager(py)?;
+            let handler = PyString::new(py, "handler").into_any();
+
+            manager.borrow().push_thread(py, handler.clone())?;
+            let objects = as_strings(py, manager.borrow().iter_context_objects(py)?)?;
+            assert_eq!(objects, vec!["handler".to_string()]);
+
+            let popped = manager.borrow().pop_thread(py)?;
+            assert!(popped.is(&handler));
+            let objects = as_strings(py, manager.borrow().iter_context_objects(py)?)?;
+            assert!(objects.is_empty());
+
+            assert!(manager.borrow().pop_thread(py).is_err());
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn iter_context_objects_merges_global_thread_and_context_tiers() -> PyResult<()> {
+        Python::initialize();
+        Python::attach(|py| {
+            let manager = new_manager(py)?;
+            let global = PyString::new(py, "global").into_any();
+            let thread = PyString::new(py, "thread").into_any();
+            let context = PyString::new(py, "context").into_any();
+
+            // Pushed in this order so the expected merge order (newest `stackop`
+            // counter first) differs from the tier order, proving the three tiers
+            // are actually merged and sorted together rather than just concatenated.
+            manager.borrow().push_context(py, context.clone())?;
+            manager.borrow().push_application(py, global.clone())?;
+            manager.borrow().push_thread(py, thread.clone())?;
+
+            let objects = as_strings(py, manager.borrow().iter_context_objects(py)?)?;
+            assert_eq!(
+                objects,
+                vec!["thread".to_string(), "global".to_string(), "context".to_string()]
+            );
+
+            manager.borrow().pop_thread(py)?;
+            manager.borrow().pop_application(py)?;
+            manager.borrow().pop_context(py)?;
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn cache_disambiguates_same_context_key_by_thread_ident() -> PyResult<()> {
+        Python::initialize();
+        Python::attach(|py| {
+            let manager = new_manager(py)?;
+
+            // Deliberately no push_context here: with nothing ever pushed,
+            // `context_stack.get(None)` returns the var's literal default
+            // `FrozenSequence` object on every thread, so the cache key is
+            // identical across threads -- only `thread_ident` can tell their
+            // merges apart.
+            let main_handler = PyString::new(py, "main-thread").into_any();
+            manager.borrow().push_thread(py, main_handler.clone())?;
+
+            let main_objects = as_strings(py, manager.borrow().iter_context_objects(py)?)?;
+            assert_eq!(main_objects, vec!["main-thread".to_string()]);
+
+            let manager_for_thread = manager.clone().unbind();
+            // Release the GIL while the other thread runs and joins -- it needs
+            // to attach its own Python thread state, which would otherwise
+            // deadlock against this thread holding the GIL for the duration of
+            // `join()`.
+            let other_objects = py.allow_threads(|| {
+                let handle = std::thread::spawn(move || -> PyResult<Vec<String>> {
+                    Python::attach(|py| {
+                        let manager = manager_for_thread.bind(py);
+                        let other_handler = PyString::new(py, "other-thread").into_any();
+                        manager.borrow().push_thread(py, other_handler)?;
+                        // No `pop_thread` here: it would clear the cache, and the
+                        // main thread's next lookup must find this thread's merge
+                        // still cached under the shared key.
+                        let objects = as_strings(py, manager.borrow().iter_context_objects(py)?)?;
+                        Ok(objects)
+                    })
+                });
+                handle.join().unwrap()
+            })?;
+            assert_eq!(other_objects, vec!["other-thread".to_string()]);
+
+            // The main thread must still see only its own thread-local handler,
+            // never the other thread's -- even though both merges were cached
+            // under the very same context `FrozenSequence` key.
+            let main_objects_again = as_strings(py, manager.borrow().iter_context_objects(py)?)?;
+            assert_eq!(main_objects_again, vec!["main-thread".to_string()]);
+
+            manager.borrow().pop_thread(py)?;
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn cache_order_stays_bounded_without_hitting_capacity() -> PyResult<()> {
+        Python::initialize();
+        Python::attach(|py| {
+            let manager = new_manager(py)?;
+            let handler = PyString::new(py, "handler").into_any();
+
+            // Each iteration pushes a distinct context (a fresh `FrozenSequence`
+            // per `push_context`) and pops it before the next one starts, so the
+            // live `cache` never grows past a handful of entries even though we
+            // run far more iterations than `MAX_CONTEXT_OBJECT_CACHE`. Before the
+            // fix, `cache_order` grew by one dead weakref per iteration anyway.
+            for _ in 0..(MAX_CONTEXT_OBJECT_CACHE * 4) {
+                manager.borrow().push_context(py, handler.clone())?;
+                manager.borrow().iter_context_objects(py)?;
+                manager.borrow().pop_context(py)?;
+            }
+
+            let order_len = manager.borrow().cache_order.lock().unwrap().len();
+            assert!(
+                order_len < MAX_CONTEXT_OBJECT_CACHE,
+                "cache_order grew unboundedly: {order_len} entries"
+            );
+
+            Ok(())
+        })
+    }
+
+    fn frozen_sequence_strings(py: Python<'_>, seq: &FrozenSequence) -> PyResult<Vec<String>> {
+        seq.items
+            .bind(py)
+            .try_iter()?
+            .map(|item| item?.extract())
+            .collect()
+    }
+
+    #[test]
+    fn frozen_sequence_concat_and_repeat() -> PyResult<()> {
+        Python::initialize();
+        Python::attach(|py| {
+            let abc = Bound::new(
+                py,
+                FrozenSequence::__new__(py, Some(PyTuple::new(py, ["a", "b", "c"])?.into_any()))?,
+            )?;
+            let de = Bound::new(
+                py,
+                FrozenSequence::__new__(py, Some(PyTuple::new(py, ["d", "e"])?.into_any()))?,
+            )?;
+
+            let combined = abc.borrow().__add__(py, de.as_any())?;
+            let combined: PyRef<'_, FrozenSequence> = combined.bind(py).extract()?;
+            assert_eq!(
+                frozen_sequence_strings(py, &combined)?,
+                vec!["a", "b", "c", "d", "e"]
+            );
+
+            // `other` need not be a `FrozenSequence` -- any iterable coerces,
+            // same as real tuple/list `+`.
+            let combined_from_list = abc.borrow().__add__(py, PyList::new(py, ["d", "e"])?.as_any())?;
+            let combined_from_list: PyRef<'_, FrozenSequence> = combined_from_list.bind(py).extract()?;
+            assert_eq!(
+                frozen_sequence_strings(py, &combined_from_list)?,
+                vec!["a", "b", "c", "d", "e"]
+            );
+
+            let radd = de.borrow().__radd__(py, abc.as_any())?;
+            let radd: PyRef<'_, FrozenSequence> = radd.bind(py).extract()?;
+            assert_eq!(frozen_sequence_strings(py, &radd)?, vec!["a", "b", "c", "d", "e"]);
+
+            // A non-iterable operand falls back to `NotImplemented`, not a raw
+            // `TypeError` leaking out of the iterator-construction attempt.
+            let five = 5i32.into_pyobject(py)?.into_any();
+            let not_implemented = abc.borrow().__add__(py, &five)?;
+            assert!(not_implemented.bind(py).is(&py.NotImplemented()));
+            let not_implemented = abc.borrow().__radd__(py, &five)?;
+            assert!(not_implemented.bind(py).is(&py.NotImplemented()));
+
+            let repeated = abc.borrow().__mul__(py, 2)?;
+            assert_eq!(
+                frozen_sequence_strings(py, &repeated)?,
+                vec!["a", "b", "c", "a", "b", "c"]
+            );
+            let rmul = abc.borrow().__rmul__(py, 2)?;
+            assert_eq!(
+                frozen_sequence_strings(py, &rmul)?,
+                frozen_sequence_strings(py, &repeated)?
+            );
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn frozen_sequence_ordering_index_and_count() -> PyResult<()> {
+        Python::initialize();
+        Python::attach(|py| {
+            let abc = Bound::new(
+                py,
+                FrozenSequence::__new__(py, Some(PyTuple::new(py, ["a", "b", "c"])?.into_any()))?,
+            )?;
+            let abb = Bound::new(
+                py,
+                FrozenSequence::__new__(py, Some(PyTuple::new(py, ["a", "b", "b"])?.into_any()))?,
+            )?;
+
+            assert!(abb.borrow().__lt__(py, &abc.borrow())?);
+            assert!(abb.borrow().__le__(py, &abc.borrow())?);
+            assert!(abb.borrow().__le__(py, &abb.borrow())?);
+            assert!(abc.borrow().__gt__(py, &abb.borrow())?);
+            assert!(abc.borrow().__ge__(py, &abb.borrow())?);
+            assert!(abc.borrow().__ge__(py, &abc.borrow())?);
+
+            let b = PyString::new(py, "b").into_any();
+            assert_eq!(abc.borrow().index(py, &b, 0, None)?, 1);
+            assert!(abc.borrow().index(py, &b, 2, None).is_err());
+            assert_eq!(abc.borrow().count(py, &b)?, 1);
+            assert_eq!(abb.borrow().count(py, &b)?, 2);
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn apply_snapshot_allows_pop_context_in_a_different_context() -> PyResult<()> {
+        Python::initialize();
+        Python::attach(|py| {
+            let manager = new_manager(py)?;
+            let handler = PyString::new(py, "handler").into_any();
+            manager.borrow().push_context(py, handler.clone())?;
+
+            let snapshot = Bound::new(py, manager.borrow().snapshot(py)?)?;
+
+            // `contextvars.Context().run(...)` executes the callable in a brand
+            // new Context, distinct from the one `push_context` ran in above —
+            // exactly the situation `apply_snapshot` is meant to support (e.g. a
+            // spawned asyncio task that doesn't inherit the caller's Context).
+            let contextvars = py.import("contextvars")?;
+            let fresh_context = contextvars.getattr("Context")?.call0()?;
+
+            let manager_for_closure = manager.clone().unbind();
+            let snapshot_for_closure = snapshot.clone().unbind();
+            let handler_for_closure = handler.clone().unbind();
+            let run_in_fresh_context = PyCFunction::new_closure(
+                py,
+                None,
+                None,
+                move |args, _kwargs| -> PyResult<()> {
+                    let py = args.py();
+                    let manager = manager_for_closure.bind(py);
+                    let apply = Bound::new(
+                        py,
+                        ContextStackManager::apply_snapshot(
+                            manager_for_closure.clone_ref(py),
+                            snapshot_for_closure.clone_ref(py),
+                        ),
+                    )?;
+                    apply.borrow().__enter__(py)?;
+                    // This is the call that used to raise ValueError: the
+                    // restored frame's token was minted in the Context that
+                    // called `snapshot()`, not this freshly entered one.
+                    let popped = manager.borrow().pop_context(py)?;
+                    assert!(popped.is(handler_for_closure.bind(py)));
+                    apply.borrow().__exit__(py, None, None, None)?;
+                    Ok(())
+                },
+            )?;
+
+            fresh_context.call_method1("run", (run_in_fresh_context,))?;
+
+            Ok(())
+        })
+    }
+}